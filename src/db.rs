@@ -1,51 +1,474 @@
 use crate::logs::format_log_chat;
+use async_trait::async_trait;
+use bb8::Pool;
+use bb8_redis::RedisConnectionManager;
 use log::info;
 use redis::AsyncCommands;
 use redis::RedisResult;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::time::Duration;
+
+/// Get redis key for chat given its identifier.
+fn get_chat_key(chat_id: i64) -> String {
+    format!("chat:{}", chat_id)
+}
+
+/// Get redis key for aliases storage.
+fn get_aliases_key(chat_id: i64) -> String {
+    get_chat_key(chat_id) + "aliases"
+}
+
+/// Get redis key for dialogues storage for given chat id.
+fn get_dialogues_key(chat_id: i64) -> String {
+    get_chat_key(chat_id) + "dialogues"
+}
+
+/// Get redis key for the reverse (sticker -> aliases) index.
+fn get_sticker_aliases_key(chat_id: i64) -> String {
+    get_chat_key(chat_id) + "sticker_aliases"
+}
+
+/// Get field name for given from_id (can be empty).
+fn get_from_field(from_id: Option<i64>) -> String {
+    from_id
+        .map(|x| x.to_string())
+        .unwrap_or("NO_ID".to_string())
+}
+
+/// An error returned from a [`StickerStore`] implementation.
+#[derive(Debug)]
+pub enum RedisStorageError {
+    SerdeError(serde_json::Error),
+
+    RedisError(redis::RedisError),
+
+    /// Returned from [`StickerStore::remove_dialogue`].
+    DialogueNotFound,
+
+    /// Returned from [`TeloxideDialogueStorage`] when asked about a
+    /// `ChatId` that didn't come from its own
+    /// [`TeloxideDialogueStorage::dialogue_key`].
+    UnknownDialogueKey,
+}
+
+impl std::fmt::Display for RedisStorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RedisStorageError::SerdeError(e) => write!(f, "serialization error: {}", e),
+            RedisStorageError::RedisError(e) => write!(f, "redis error: {}", e),
+            RedisStorageError::DialogueNotFound => write!(f, "dialogue not found"),
+            RedisStorageError::UnknownDialogueKey => {
+                write!(f, "dialogue key was not produced by TeloxideDialogueStorage::dialogue_key")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RedisStorageError {}
+
+/// Storage backend for sticker aliases and per-user dialogue state.
+///
+/// Pulled out of [`RedisConnection`] so alias/dialogue logic can be
+/// exercised against an in-memory [`mock::MockStickerStore`] in tests,
+/// without a live Redis instance.
+#[async_trait]
+pub trait StickerStore {
+    /// Store alias-sticker mapping.
+    ///
+    /// If the alias is already tied to some sticker, overwrite it so the alias will be mapped to a new
+    /// sticker (for given `chat_id`).
+    async fn set_alias(
+        &self,
+        chat_id: i64,
+        alias: &str,
+        sticker_id: &str,
+    ) -> Result<(), RedisStorageError>;
+
+    /// Set multiple aliases for a sticker.
+    ///
+    /// Stops and returns the first error encountered, leaving any
+    /// aliases already set in place.
+    async fn set_aliases(
+        &self,
+        chat_id: i64,
+        aliases: &[&str],
+        sticker_id: &str,
+    ) -> Result<(), RedisStorageError> {
+        for alias in aliases {
+            self.set_alias(chat_id, alias, sticker_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Obtain sticker id for given alias in the chat (if any).
+    ///
+    /// Returns `Ok(None)` only when the alias is genuinely not mapped to
+    /// any sticker; a connection/protocol failure is returned as `Err`.
+    async fn get_sticker_id(
+        &self,
+        chat_id: i64,
+        alias: &str,
+    ) -> Result<Option<String>, RedisStorageError>;
+
+    /// Unmap (remove) the alias for given chat id.
+    async fn remove_alias(&self, chat_id: i64, alias: &str) -> Result<(), RedisStorageError>;
+
+    /// Get every alias currently mapped to `sticker_id` in the chat.
+    async fn get_aliases_for_sticker(
+        &self,
+        chat_id: i64,
+        sticker_id: &str,
+    ) -> Result<Vec<String>, RedisStorageError>;
+
+    /// Remove every alias mapped to `sticker_id`.
+    ///
+    /// Updates the forward (alias -> sticker) and reverse (sticker ->
+    /// aliases) indexes atomically, so they can't drift relative to each
+    /// other.
+    async fn remove_sticker(&self, chat_id: i64, sticker_id: &str) -> Result<(), RedisStorageError>;
+
+    /// Update a dialogue in the storage.
+    ///
+    /// Saves the `dialogue` in the storage for given chat and user.
+    async fn update_dialogue<D>(
+        &self,
+        chat_id: i64,
+        from_id: Option<i64>,
+        dialogue: D,
+    ) -> Result<(), RedisStorageError>
+    where
+        D: Serialize + Send + 'static;
+
+    /// Retrieve a dialogue from the storage.
+    ///
+    /// Gives the `dialogue` for given chat and user.
+    async fn get_dialogue<D>(
+        &self,
+        chat_id: i64,
+        from_id: Option<i64>,
+    ) -> Result<Option<D>, RedisStorageError>
+    where
+        D: DeserializeOwned;
+
+    /// Remove dialogue.
+    async fn remove_dialogue(
+        &self,
+        chat_id: i64,
+        from_id: Option<i64>,
+    ) -> Result<(), RedisStorageError>;
+}
+
+/// Default number of connections kept open in the pool.
+const DEFAULT_POOL_MAX_SIZE: u32 = 16;
+
+/// Default timeout for checking out a connection from the pool.
+const DEFAULT_CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Username/password to authenticate with via Redis ACL (or the legacy
+/// `requirepass`, by leaving `username` unset).
+pub struct RedisCredentials {
+    pub username: Option<String>,
+    pub password: String,
+}
+
+/// TLS settings for the connection, gated behind this crate's `tls`
+/// feature (which enables the `redis` crate's `tokio-rustls-comp`).
+///
+/// Without the `tls` feature, [`RedisConnectionConfig::tls`] must be
+/// [`RedisTlsConfig::Disabled`] or [`RedisConnection::with_config`]
+/// fails eagerly instead of silently connecting in plaintext.
+///
+/// Custom CA certificates aren't supported: the pool reconnects via
+/// `redis::Client::open`, which only ever honours the system trust
+/// store, and `Client::build_with_tls` (the only way to plug in a custom
+/// CA) isn't reachable through that reconnect path. Use a CA already
+/// trusted by the system, or a proxy/sidecar that terminates TLS with
+/// one.
+#[derive(Default)]
+pub enum RedisTlsConfig {
+    #[default]
+    Disabled,
+    /// Connect over TLS (`rediss://`), trusting the system's CA store.
+    /// Works against both Redis and Valkey, since both speak the same
+    /// wire protocol.
+    Enabled,
+}
+
+/// Configuration for [`RedisConnection::with_config`].
+pub struct RedisConnectionConfig {
+    /// Maximum number of connections the pool is allowed to hold open.
+    pub max_size: u32,
+
+    /// How long to wait for a connection to become available before
+    /// giving up.
+    pub connection_timeout: Duration,
+
+    /// TLS settings; by default no override is applied, so the url's
+    /// scheme decides (`redis://` plaintext, `rediss://` TLS).
+    pub tls: RedisTlsConfig,
+
+    /// ACL (or `requirepass`) credentials; defaults to none.
+    pub credentials: Option<RedisCredentials>,
+}
+
+impl Default for RedisConnectionConfig {
+    fn default() -> Self {
+        RedisConnectionConfig {
+            max_size: DEFAULT_POOL_MAX_SIZE,
+            connection_timeout: DEFAULT_CONNECTION_TIMEOUT,
+            tls: RedisTlsConfig::default(),
+            credentials: None,
+        }
+    }
+}
+
+/// Build a [`redis::ConnectionInfo`] from a `redis(s)://` url, applying
+/// TLS and ACL credential overrides from `tls`/`credentials` on top.
+fn build_connection_info(
+    redis_ip: &str,
+    tls: &RedisTlsConfig,
+    credentials: &Option<RedisCredentials>,
+) -> redis::RedisResult<redis::ConnectionInfo> {
+    use redis::IntoConnectionInfo;
+    let mut info = redis_ip.into_connection_info()?;
+
+    match tls {
+        RedisTlsConfig::Disabled => {}
+        RedisTlsConfig::Enabled => {
+            enable_tls(&mut info)?;
+        }
+    }
+
+    if let Some(credentials) = credentials {
+        info.redis.username = credentials.username.clone();
+        info.redis.password = Some(credentials.password.clone());
+    }
+
+    Ok(info)
+}
+
+#[cfg(feature = "tls")]
+fn enable_tls(info: &mut redis::ConnectionInfo) -> redis::RedisResult<()> {
+    if let redis::ConnectionAddr::Tcp(host, port) = &info.addr {
+        info.addr = redis::ConnectionAddr::TcpTls {
+            host: host.clone(),
+            port: *port,
+            insecure: false,
+            tls_params: None,
+        };
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "tls"))]
+fn enable_tls(_info: &mut redis::ConnectionInfo) -> redis::RedisResult<()> {
+    Err(redis::RedisError::from((
+        redis::ErrorKind::InvalidClientConfig,
+        "TLS was requested, but this crate was built without the `tls` feature",
+    )))
+}
 
 /* TODO: wrap connection in trait (not trivial with async). */
 /// Redis connection representation.
 ///
 /// Provides simple interface for storing sticker aliases and dialogue
-/// state (with serialization).
+/// state (with serialization). Internally backed by a connection pool,
+/// so cloning this value (cheap, see `#[derive(Clone)]`) and using the
+/// clones from multiple tasks lets unrelated chats be served in parallel
+/// instead of contending on one connection. The manager reconnects
+/// lazily, so a transient Redis restart does not take the bot down.
+#[derive(Clone)]
 pub struct RedisConnection {
-    connection: redis::aio::Connection,
+    pool: Pool<RedisConnectionManager>,
 }
 
 // General implementation
 impl RedisConnection {
-    /// Create new connection to redis server in specified ip.
+    /// Create new connection pool to redis server in specified ip, using
+    /// default pool settings (see [`RedisConnectionConfig`]).
     ///
     /// IP should be formatted according to `redis` crate requirements
-    /// (currently similar to `redis://127.0.0.1/`)
+    /// (currently similar to `redis://127.0.0.1/`, or `rediss://...` for
+    /// TLS — see [`RedisConnectionConfig`] if you also need ACL
+    /// credentials; custom CA certificates are not supported).
     pub async fn new(redis_ip: &str) -> redis::RedisResult<RedisConnection> {
-        let client = redis::Client::open(redis_ip)?;
-        let con = client.get_async_connection().await?;
-        Ok(RedisConnection { connection: con })
+        RedisConnection::with_config(redis_ip, RedisConnectionConfig::default()).await
     }
 
-    /// Get redis key for chat given its identifier.
-    fn get_chat_key(chat_id: i64) -> String {
-        format!("chat:{}", chat_id)
+    /// Create new connection pool to redis server in specified ip, with
+    /// custom pool size / checkout timeout / TLS / credentials.
+    ///
+    /// Works against Valkey the same way, since it's wire-compatible
+    /// with Redis.
+    pub async fn with_config(
+        redis_ip: &str,
+        config: RedisConnectionConfig,
+    ) -> redis::RedisResult<RedisConnection> {
+        let connection_info = build_connection_info(redis_ip, &config.tls, &config.credentials)?;
+        let manager = RedisConnectionManager::new(connection_info)?;
+        let pool = Pool::builder()
+            .max_size(config.max_size)
+            .connection_timeout(config.connection_timeout)
+            .build(manager)
+            .await?;
+        Ok(RedisConnection { pool })
     }
-}
 
-impl RedisConnection {
-    /// Get redis key for aliases storage.
-    fn get_aliases_key(chat_id: i64) -> String {
-        RedisConnection::get_chat_key(chat_id) + "aliases"
+    /// Check out a connection from the pool.
+    ///
+    /// The manager behind the pool re-establishes broken connections
+    /// lazily, so this only fails if the pool could not hand out a
+    /// working connection within its configured timeout.
+    async fn connection(
+        &self,
+    ) -> RedisResult<bb8::PooledConnection<'_, RedisConnectionManager>> {
+        self.pool.get().await.map_err(|e| match e {
+            bb8::RunError::User(e) => e,
+            bb8::RunError::TimedOut => redis::RedisError::from((
+                redis::ErrorKind::IoError,
+                "timed out while checking out a pooled redis connection",
+            )),
+        })
     }
+}
 
-    /// Store alias-sticker mapping in redis.
-    ///
-    /// If the alias is already tied to some sticker, overwrite it so the alias will be mapped to a new
-    /// sticker (for given `chat_id`).
-    pub async fn set_alias(&mut self, chat_id: i64, alias: &str, sticker_id: &str) {
-        let key: String = RedisConnection::get_aliases_key(chat_id);
-        let set_result: RedisResult<()> = self.connection.hset(key, alias, sticker_id).await;
-        match set_result {
+/// A Lua script that atomically removes every alias pointing at a
+/// sticker from the forward index and drops its reverse index entry, so
+/// the two can never drift relative to each other.
+///
+/// `KEYS[1]` is the forward (alias -> sticker) hash, `KEYS[2]` the
+/// reverse (sticker -> aliases) hash, `ARGV[1]` the sticker id.
+const REMOVE_STICKER_SCRIPT: &str = r#"
+local aliases_json = redis.call('HGET', KEYS[2], ARGV[1])
+if aliases_json then
+    local aliases = cjson.decode(aliases_json)
+    for _, alias in ipairs(aliases) do
+        redis.call('HDEL', KEYS[1], alias)
+    end
+end
+redis.call('HDEL', KEYS[2], ARGV[1])
+return 1
+"#;
+
+/// Read the aliases currently stored for `sticker_id` in the reverse
+/// index (`reverse_key`).
+async fn reverse_index_read(
+    con: &mut bb8::PooledConnection<'_, RedisConnectionManager>,
+    reverse_key: &str,
+    sticker_id: &str,
+) -> Result<Vec<String>, RedisStorageError> {
+    let raw: Option<String> = con
+        .hget(reverse_key, sticker_id)
+        .await
+        .map_err(RedisStorageError::RedisError)?;
+    raw.map(|v| serde_json::from_str(&v))
+        .transpose()
+        .map(Option::unwrap_or_default)
+        .map_err(RedisStorageError::SerdeError)
+}
+
+/// A Lua script that atomically points `alias` at `sticker_id`: drops it
+/// from whatever sticker it used to be mapped to in the reverse index
+/// (if any), adds it to the new sticker's reverse entry, and updates the
+/// forward mapping — so a concurrent `set_alias`/`remove_alias` for the
+/// same sticker can't race on the reverse index's read-modify-write and
+/// silently drop an alias.
+///
+/// `KEYS[1]` is the forward (alias -> sticker) hash, `KEYS[2]` the
+/// reverse (sticker -> aliases) hash, `ARGV[1]` the alias, `ARGV[2]` the
+/// sticker id to map it to.
+const SET_ALIAS_SCRIPT: &str = r#"
+local previous_sticker = redis.call('HGET', KEYS[1], ARGV[1])
+if previous_sticker and previous_sticker ~= ARGV[2] then
+    local prev_json = redis.call('HGET', KEYS[2], previous_sticker)
+    if prev_json then
+        local prev_aliases = cjson.decode(prev_json)
+        local kept = {}
+        for _, a in ipairs(prev_aliases) do
+            if a ~= ARGV[1] then
+                table.insert(kept, a)
+            end
+        end
+        if #kept == 0 then
+            redis.call('HDEL', KEYS[2], previous_sticker)
+        else
+            redis.call('HSET', KEYS[2], previous_sticker, cjson.encode(kept))
+        end
+    end
+end
+
+local aliases_json = redis.call('HGET', KEYS[2], ARGV[2])
+local aliases = aliases_json and cjson.decode(aliases_json) or {}
+local already_present = false
+for _, a in ipairs(aliases) do
+    if a == ARGV[1] then
+        already_present = true
+    end
+end
+if not already_present then
+    table.insert(aliases, ARGV[1])
+end
+redis.call('HSET', KEYS[2], ARGV[2], cjson.encode(aliases))
+redis.call('HSET', KEYS[1], ARGV[1], ARGV[2])
+return 1
+"#;
+
+/// A Lua script that atomically unmaps `alias` and drops it from its
+/// sticker's reverse index entry, for the same reason [`SET_ALIAS_SCRIPT`]
+/// does the equivalent for `set_alias`.
+///
+/// `KEYS[1]` is the forward (alias -> sticker) hash, `KEYS[2]` the
+/// reverse (sticker -> aliases) hash, `ARGV[1]` the alias.
+const REMOVE_ALIAS_SCRIPT: &str = r#"
+local sticker_id = redis.call('HGET', KEYS[1], ARGV[1])
+if sticker_id then
+    local aliases_json = redis.call('HGET', KEYS[2], sticker_id)
+    if aliases_json then
+        local aliases = cjson.decode(aliases_json)
+        local kept = {}
+        for _, a in ipairs(aliases) do
+            if a ~= ARGV[1] then
+                table.insert(kept, a)
+            end
+        end
+        if #kept == 0 then
+            redis.call('HDEL', KEYS[2], sticker_id)
+        else
+            redis.call('HSET', KEYS[2], sticker_id, cjson.encode(kept))
+        end
+    end
+    redis.call('HDEL', KEYS[1], ARGV[1])
+end
+return 1
+"#;
+
+#[async_trait]
+impl StickerStore for RedisConnection {
+    async fn set_alias(
+        &self,
+        chat_id: i64,
+        alias: &str,
+        sticker_id: &str,
+    ) -> Result<(), RedisStorageError> {
+        let key: String = get_aliases_key(chat_id);
+        let reverse_key: String = get_sticker_aliases_key(chat_id);
+        let set_result: Result<(), RedisStorageError> = async {
+            let mut con = self
+                .connection()
+                .await
+                .map_err(RedisStorageError::RedisError)?;
+            redis::Script::new(SET_ALIAS_SCRIPT)
+                .key(&key)
+                .key(&reverse_key)
+                .arg(alias)
+                .arg(sticker_id)
+                .invoke_async(&mut *con)
+                .await
+                .map_err(RedisStorageError::RedisError)
+        }
+        .await;
+        match &set_result {
             Ok(_) => {
                 info!(
                     "{}",
@@ -58,28 +481,26 @@ impl RedisConnection {
             Err(e) => {
                 info!(
                     "{}",
-                    format_log_chat(&format!("Failed to save alias to DB: {}", e), chat_id)
+                    format_log_chat(&format!("Failed to save alias to DB: {:?}", e), chat_id)
                 );
             }
         }
+        set_result
     }
 
-    /// Set multiple aliases for a sticker.
-    pub async fn set_aliases<'a, T>(&mut self, chat_id: i64, aliases: T, sticker_id: &str)
-    where
-        T: IntoIterator<Item = &'a str>,
-    {
-        for alias in aliases {
-            self.set_alias(chat_id, alias, sticker_id).await;
+    async fn get_sticker_id(
+        &self,
+        chat_id: i64,
+        alias: &str,
+    ) -> Result<Option<String>, RedisStorageError> {
+        let key: String = get_aliases_key(chat_id);
+        let get_result: RedisResult<Option<String>> = async {
+            let mut con = self.connection().await?;
+            con.hget(key, alias).await
         }
-    }
-
-    /// Obtain sticker id for given alias in the chat (if any).
-    pub async fn get_sticker_id(&mut self, chat_id: i64, alias: &str) -> Option<String> {
-        let key: String = RedisConnection::get_aliases_key(chat_id);
-        let set_result: RedisResult<String> = self.connection.hget(key, alias).await;
-        match set_result {
-            Ok(sticker_id) => {
+        .await;
+        match &get_result {
+            Ok(Some(sticker_id)) => {
                 info!(
                     "{}",
                     format_log_chat(
@@ -87,7 +508,12 @@ impl RedisConnection {
                         chat_id
                     )
                 );
-                Some(sticker_id)
+            }
+            Ok(None) => {
+                info!(
+                    "{}",
+                    format_log_chat(&format!("No sticker found for alias '{a}'", a = alias), chat_id)
+                );
             }
             Err(e) => {
                 info!(
@@ -97,18 +523,29 @@ impl RedisConnection {
                         chat_id
                     )
                 );
-                None
             }
         }
+        get_result.map_err(RedisStorageError::RedisError)
     }
 
-    // TODO: add support of sticker removal
-    #[allow(dead_code)]
-    /// Unmap (remove) the alias for given chat id.
-    pub async fn remove_alias(&mut self, chat_id: i64, alias: &str) {
-        let key: String = RedisConnection::get_aliases_key(chat_id);
-        let del_result: RedisResult<()> = self.connection.hdel(key, alias).await;
-        match del_result {
+    async fn remove_alias(&self, chat_id: i64, alias: &str) -> Result<(), RedisStorageError> {
+        let key: String = get_aliases_key(chat_id);
+        let reverse_key: String = get_sticker_aliases_key(chat_id);
+        let del_result: Result<(), RedisStorageError> = async {
+            let mut con = self
+                .connection()
+                .await
+                .map_err(RedisStorageError::RedisError)?;
+            redis::Script::new(REMOVE_ALIAS_SCRIPT)
+                .key(&key)
+                .key(&reverse_key)
+                .arg(alias)
+                .invoke_async(&mut *con)
+                .await
+                .map_err(RedisStorageError::RedisError)
+        }
+        .await;
+        match &del_result {
             Ok(_) => {
                 info!(
                     "{}",
@@ -118,55 +555,98 @@ impl RedisConnection {
             Err(e) => {
                 info!(
                     "{}",
-                    format_log_chat(&format!("Failed to remove alias from DB: {}", e), chat_id)
+                    format_log_chat(&format!("Failed to remove alias from DB: {:?}", e), chat_id)
                 );
             }
         }
+        del_result
     }
-}
-
-/// An error returned from `Storage` implementation.
-#[derive(Debug)]
-pub enum RedisStorageError {
-    SerdeError(serde_json::Error),
 
-    RedisError(redis::RedisError),
-
-    /// Returned from [`RedisStorage::remove_dialogue`].
-    DialogueNotFound,
-}
-
-/// Dialogue storage.
-///
-/// Similar to `teloxide::dispatching::dialogue::Storage`, but with different dialogue for each user
-/// in the chat.
-impl RedisConnection {
-    /// Get redis key for dialogues storage for given chat id.
-    fn get_dialogues_key(chat_id: i64) -> String {
-        RedisConnection::get_chat_key(chat_id) + "dialogues"
+    async fn get_aliases_for_sticker(
+        &self,
+        chat_id: i64,
+        sticker_id: &str,
+    ) -> Result<Vec<String>, RedisStorageError> {
+        let reverse_key: String = get_sticker_aliases_key(chat_id);
+        let get_result: Result<Vec<String>, RedisStorageError> = async {
+            let mut con = self
+                .connection()
+                .await
+                .map_err(RedisStorageError::RedisError)?;
+            reverse_index_read(&mut con, &reverse_key, sticker_id).await
+        }
+        .await;
+        match &get_result {
+            Ok(aliases) => {
+                info!(
+                    "{}",
+                    format_log_chat(
+                        &format!(
+                            "Retrieved {n} alias(es) for sticker '{s}'",
+                            n = aliases.len(),
+                            s = sticker_id
+                        ),
+                        chat_id
+                    )
+                );
+            }
+            Err(e) => {
+                info!(
+                    "{}",
+                    format_log_chat(
+                        &format!("Failed to list aliases for sticker '{}': {:?}", sticker_id, e),
+                        chat_id
+                    )
+                );
+            }
+        }
+        get_result
     }
 
-    /// Get field name for given from_id (can be empty).
-    fn get_from_field(from_id: Option<i64>) -> String {
-        from_id
-            .map(|x| x.to_string())
-            .unwrap_or("NO_ID".to_string())
+    async fn remove_sticker(&self, chat_id: i64, sticker_id: &str) -> Result<(), RedisStorageError> {
+        let key: String = get_aliases_key(chat_id);
+        let reverse_key: String = get_sticker_aliases_key(chat_id);
+        let result: RedisResult<()> = async {
+            let mut con = self.connection().await?;
+            redis::Script::new(REMOVE_STICKER_SCRIPT)
+                .key(&key)
+                .key(&reverse_key)
+                .arg(sticker_id)
+                .invoke_async(&mut *con)
+                .await
+        }
+        .await;
+        match &result {
+            Ok(_) => {
+                info!(
+                    "{}",
+                    format_log_chat(&format!("Removed sticker '{}'", sticker_id), chat_id)
+                );
+            }
+            Err(e) => {
+                info!(
+                    "{}",
+                    format_log_chat(
+                        &format!("Failed to remove sticker '{}': {}", sticker_id, e),
+                        chat_id
+                    )
+                );
+            }
+        }
+        result.map_err(RedisStorageError::RedisError)
     }
 
-    /// Update a dialogue in the storage.
-    ///
-    /// Saves the `dialogue` in the redis database for given chat and user.
-    pub async fn update_dialogue<'a, D>(
-        &mut self,
+    async fn update_dialogue<D>(
+        &self,
         chat_id: i64,
         from_id: Option<i64>,
         dialogue: D,
     ) -> Result<(), RedisStorageError>
     where
-        D: Serialize,
+        D: Serialize + Send + 'static,
     {
-        let key: String = RedisConnection::get_dialogues_key(chat_id);
-        let field: String = RedisConnection::get_from_field(from_id);
+        let key: String = get_dialogues_key(chat_id);
+        let field: String = get_from_field(from_id);
 
         // Serialize
         let value: String = serde_json::to_string(&dialogue).map_err(|err| {
@@ -178,7 +658,11 @@ impl RedisConnection {
         })?;
 
         // Save
-        let set_result: RedisResult<()> = self.connection.hset(&key, &field, &value).await;
+        let set_result: RedisResult<()> = async {
+            let mut con = self.connection().await?;
+            con.hset(&key, &field, &value).await
+        }
+        .await;
         match &set_result {
             Ok(_) => {
                 info!(
@@ -196,26 +680,24 @@ impl RedisConnection {
         set_result.map_err(RedisStorageError::RedisError)
     }
 
-    /// Retrieve a dialogue from the storage.
-    ///
-    /// Givethe `dialogue` for given chat and user.
-    pub async fn get_dialogue<'a, D>(
-        &mut self,
+    async fn get_dialogue<D>(
+        &self,
         chat_id: i64,
         from_id: Option<i64>,
     ) -> Result<Option<D>, RedisStorageError>
     where
         D: DeserializeOwned,
     {
-        let key: String = RedisConnection::get_dialogues_key(chat_id);
-        let field: String = RedisConnection::get_from_field(from_id);
+        let key: String = get_dialogues_key(chat_id);
+        let field: String = get_from_field(from_id);
 
         // Retrieve from DB
-        let value: Option<String> = self
-            .connection
-            .hget(&key, &field)
-            .await
-            .map_err(RedisStorageError::RedisError)?;
+        let value: Option<String> = async {
+            let mut con = self.connection().await?;
+            con.hget(&key, &field).await
+        }
+        .await
+        .map_err(RedisStorageError::RedisError)?;
 
         // Deserialize
         let value: Result<Option<D>, RedisStorageError> = value
@@ -225,16 +707,19 @@ impl RedisConnection {
         value
     }
 
-    /// Remove dialogue.
-    pub async fn remove_dialogue(
-        &mut self,
+    async fn remove_dialogue(
+        &self,
         chat_id: i64,
         from_id: Option<i64>,
     ) -> Result<(), RedisStorageError> {
-        let key: String = RedisConnection::get_dialogues_key(chat_id);
-        let field: String = RedisConnection::get_from_field(from_id);
+        let key: String = get_dialogues_key(chat_id);
+        let field: String = get_from_field(from_id);
 
-        let del_res: RedisResult<i64> = self.connection.hdel(key, field).await;
+        let del_res: RedisResult<i64> = async {
+            let mut con = self.connection().await?;
+            con.hdel(key, field).await
+        }
+        .await;
         match del_res {
             Ok(0) => Err(RedisStorageError::DialogueNotFound),
             Ok(_) => Ok(()),
@@ -242,3 +727,573 @@ impl RedisConnection {
         }
     }
 }
+
+/// Bidirectional, collision-free mapping between `(chat_id, from_id)`
+/// pairs and the single [`ChatId`] that
+/// `teloxide::dispatching::dialogue::Storage` keys dialogues by.
+///
+/// A previous version of this tried to pack both ids into one `i64` by
+/// shifting `chat_id` into the high 32 bits. That's wrong: Telegram
+/// supergroup/channel ids are routinely outside 32 bits, so the shift
+/// silently drops their high bits and two unrelated chats can collide
+/// onto the same packed key. A real lookup table sidesteps that by
+/// construction — each pair gets its own never-reused synthetic id.
+#[derive(Default)]
+struct DialogueKeyRegistry {
+    by_pair: std::collections::HashMap<(i64, Option<i64>), teloxide::types::ChatId>,
+    by_key: std::collections::HashMap<teloxide::types::ChatId, (i64, Option<i64>)>,
+    next_id: i64,
+}
+
+impl DialogueKeyRegistry {
+    /// Look up the `ChatId` previously assigned to `(chat_id, from_id)`,
+    /// assigning and remembering a fresh one on first use.
+    fn key_for(&mut self, chat_id: i64, from_id: Option<i64>) -> teloxide::types::ChatId {
+        *self.by_pair.entry((chat_id, from_id)).or_insert_with(|| {
+            let key = teloxide::types::ChatId(self.next_id);
+            self.next_id += 1;
+            self.by_key.insert(key, (chat_id, from_id));
+            key
+        })
+    }
+
+    /// Inverse of [`DialogueKeyRegistry::key_for`].
+    fn pair_for(&self, key: teloxide::types::ChatId) -> Option<(i64, Option<i64>)> {
+        self.by_key.get(&key).copied()
+    }
+
+    /// Drop `key` and its pair from both maps, so a removed dialogue
+    /// doesn't keep its entry alive in the registry forever.
+    fn evict(&mut self, key: teloxide::types::ChatId) {
+        if let Some(pair) = self.by_key.remove(&key) {
+            self.by_pair.remove(&pair);
+        }
+    }
+}
+
+/// Adapts a [`StickerStore`]'s per-user-in-chat dialogues to
+/// `teloxide::dispatching::dialogue::Storage`, so they can be plugged
+/// straight into teloxide's dispatcher (e.g. `dialogues_repl`).
+///
+/// Dialogue keys passed to the dispatcher must come from
+/// [`TeloxideDialogueStorage::dialogue_key`]; see [`DialogueKeyRegistry`]
+/// for why a packed or plain `ChatId` isn't enough here.
+pub struct TeloxideDialogueStorage<S> {
+    store: std::sync::Arc<S>,
+    keys: std::sync::Mutex<DialogueKeyRegistry>,
+}
+
+impl<S> TeloxideDialogueStorage<S> {
+    pub fn new(store: std::sync::Arc<S>) -> std::sync::Arc<Self> {
+        std::sync::Arc::new(TeloxideDialogueStorage {
+            store,
+            keys: std::sync::Mutex::new(DialogueKeyRegistry::default()),
+        })
+    }
+
+    /// Get the `ChatId` to hand to the dispatcher's dialogue storage for
+    /// this `(chat_id, from_id)` pair (instead of `update.chat.id` alone)
+    /// to get a dialogue per user in the chat.
+    pub fn dialogue_key(&self, chat_id: i64, from_id: Option<i64>) -> teloxide::types::ChatId {
+        self.keys.lock().unwrap().key_for(chat_id, from_id)
+    }
+}
+
+impl<S, D> teloxide::dispatching::dialogue::Storage<D> for TeloxideDialogueStorage<S>
+where
+    S: StickerStore + Send + Sync + 'static,
+    D: Serialize + DeserializeOwned + Send + 'static,
+{
+    type Error = RedisStorageError;
+
+    fn remove_dialogue(
+        self: std::sync::Arc<Self>,
+        chat_id: teloxide::types::ChatId,
+    ) -> futures::future::BoxFuture<'static, Result<(), Self::Error>> {
+        Box::pin(async move {
+            let Some((pair_chat_id, from_id)) = self.keys.lock().unwrap().pair_for(chat_id) else {
+                return Err(RedisStorageError::UnknownDialogueKey);
+            };
+            let result = self.store.remove_dialogue(pair_chat_id, from_id).await;
+            // The session this key stood for is over either way (found or
+            // not); keep the registry from growing for the life of the
+            // process by forgetting it now.
+            self.keys.lock().unwrap().evict(chat_id);
+            result
+        })
+    }
+
+    fn update_dialogue(
+        self: std::sync::Arc<Self>,
+        chat_id: teloxide::types::ChatId,
+        dialogue: D,
+    ) -> futures::future::BoxFuture<'static, Result<(), Self::Error>> {
+        Box::pin(async move {
+            let Some((chat_id, from_id)) = self.keys.lock().unwrap().pair_for(chat_id) else {
+                return Err(RedisStorageError::UnknownDialogueKey);
+            };
+            self.store.update_dialogue(chat_id, from_id, dialogue).await
+        })
+    }
+
+    fn get_dialogue(
+        self: std::sync::Arc<Self>,
+        chat_id: teloxide::types::ChatId,
+    ) -> futures::future::BoxFuture<'static, Result<Option<D>, Self::Error>> {
+        Box::pin(async move {
+            let Some((chat_id, from_id)) = self.keys.lock().unwrap().pair_for(chat_id) else {
+                return Err(RedisStorageError::UnknownDialogueKey);
+            };
+            self.store.get_dialogue(chat_id, from_id).await
+        })
+    }
+}
+
+/// In-memory [`StickerStore`] for exercising alias/dialogue logic in
+/// tests without a live Redis instance.
+pub mod mock {
+    use super::{
+        get_aliases_key, get_dialogues_key, get_from_field, get_sticker_aliases_key,
+        RedisStorageError, StickerStore,
+    };
+    use async_trait::async_trait;
+    use serde::de::DeserializeOwned;
+    use serde::Serialize;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// A failure to inject on the next fallible [`MockStickerStore`] operation.
+    #[derive(Debug, Clone, Copy)]
+    pub enum ForcedFailure {
+        /// Simulate a Redis connection/protocol error.
+        Redis,
+        /// Simulate a stored value that fails to deserialize (a partial
+        /// or malformed response).
+        Malformed,
+    }
+
+    /// `HashMap`-backed [`StickerStore`], keyed the same way as
+    /// [`super::RedisConnection`] (`chat:{id}aliases`, `chat:{id}dialogues`).
+    ///
+    /// Call [`MockStickerStore::inject_failure`] before an operation to make
+    /// it fail as if Redis had returned an error or a malformed value,
+    /// without touching the in-memory maps.
+    #[derive(Default)]
+    pub struct MockStickerStore {
+        aliases: Mutex<HashMap<String, HashMap<String, String>>>,
+        sticker_aliases: Mutex<HashMap<String, HashMap<String, Vec<String>>>>,
+        dialogues: Mutex<HashMap<String, HashMap<String, String>>>,
+        forced_failure: Mutex<Option<ForcedFailure>>,
+    }
+
+    impl MockStickerStore {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Make the next fallible operation act on `failure` instead of
+        /// touching the in-memory maps. Operations that don't read a stored
+        /// value report either kind as a Redis error; `get_sticker_id` and
+        /// `get_aliases_for_sticker` report [`ForcedFailure::Malformed`] as
+        /// an empty `Ok` result.
+        pub fn inject_failure(&self, failure: ForcedFailure) {
+            *self.forced_failure.lock().unwrap() = Some(failure);
+        }
+
+        fn take_forced_failure(&self) -> Option<ForcedFailure> {
+            self.forced_failure.lock().unwrap().take()
+        }
+
+        fn forced_redis_error() -> RedisStorageError {
+            RedisStorageError::RedisError(redis::RedisError::from((
+                redis::ErrorKind::IoError,
+                "forced failure injected by MockStickerStore",
+            )))
+        }
+
+        fn reverse_index_add_alias(&self, reverse_key: &str, sticker_id: &str, alias: &str) {
+            let mut sticker_aliases = self.sticker_aliases.lock().unwrap();
+            let aliases = sticker_aliases
+                .entry(reverse_key.to_string())
+                .or_default()
+                .entry(sticker_id.to_string())
+                .or_default();
+            if !aliases.iter().any(|a| a == alias) {
+                aliases.push(alias.to_string());
+            }
+        }
+
+        fn reverse_index_remove_alias(&self, reverse_key: &str, sticker_id: &str, alias: &str) {
+            if let Some(aliases) = self
+                .sticker_aliases
+                .lock()
+                .unwrap()
+                .get_mut(reverse_key)
+                .and_then(|stickers| stickers.get_mut(sticker_id))
+            {
+                aliases.retain(|a| a != alias);
+            }
+        }
+    }
+
+    #[async_trait]
+    impl StickerStore for MockStickerStore {
+        async fn set_alias(
+            &self,
+            chat_id: i64,
+            alias: &str,
+            sticker_id: &str,
+        ) -> Result<(), RedisStorageError> {
+            if self.take_forced_failure().is_some() {
+                return Err(Self::forced_redis_error());
+            }
+            let key = get_aliases_key(chat_id);
+            let reverse_key = get_sticker_aliases_key(chat_id);
+
+            let previous_sticker = self
+                .aliases
+                .lock()
+                .unwrap()
+                .get(&key)
+                .and_then(|aliases| aliases.get(alias))
+                .cloned();
+            if let Some(previous_sticker) = &previous_sticker {
+                if previous_sticker != sticker_id {
+                    self.reverse_index_remove_alias(&reverse_key, previous_sticker, alias);
+                }
+            }
+            self.reverse_index_add_alias(&reverse_key, sticker_id, alias);
+
+            self.aliases
+                .lock()
+                .unwrap()
+                .entry(key)
+                .or_default()
+                .insert(alias.to_string(), sticker_id.to_string());
+            Ok(())
+        }
+
+        async fn get_sticker_id(
+            &self,
+            chat_id: i64,
+            alias: &str,
+        ) -> Result<Option<String>, RedisStorageError> {
+            if let Some(failure) = self.take_forced_failure() {
+                return match failure {
+                    ForcedFailure::Redis => Err(Self::forced_redis_error()),
+                    // A partial read: report the alias as unmapped even if
+                    // it is actually present.
+                    ForcedFailure::Malformed => Ok(None),
+                };
+            }
+            let key = get_aliases_key(chat_id);
+            Ok(self
+                .aliases
+                .lock()
+                .unwrap()
+                .get(&key)
+                .and_then(|aliases| aliases.get(alias))
+                .cloned())
+        }
+
+        async fn remove_alias(&self, chat_id: i64, alias: &str) -> Result<(), RedisStorageError> {
+            if self.take_forced_failure().is_some() {
+                return Err(Self::forced_redis_error());
+            }
+            let key = get_aliases_key(chat_id);
+            let reverse_key = get_sticker_aliases_key(chat_id);
+            let sticker_id = self
+                .aliases
+                .lock()
+                .unwrap()
+                .get_mut(&key)
+                .and_then(|aliases| aliases.remove(alias));
+            if let Some(sticker_id) = &sticker_id {
+                self.reverse_index_remove_alias(&reverse_key, sticker_id, alias);
+            }
+            Ok(())
+        }
+
+        async fn get_aliases_for_sticker(
+            &self,
+            chat_id: i64,
+            sticker_id: &str,
+        ) -> Result<Vec<String>, RedisStorageError> {
+            if let Some(failure) = self.take_forced_failure() {
+                return match failure {
+                    ForcedFailure::Redis => Err(Self::forced_redis_error()),
+                    ForcedFailure::Malformed => Ok(Vec::new()),
+                };
+            }
+            let reverse_key = get_sticker_aliases_key(chat_id);
+            Ok(self
+                .sticker_aliases
+                .lock()
+                .unwrap()
+                .get(&reverse_key)
+                .and_then(|stickers| stickers.get(sticker_id))
+                .cloned()
+                .unwrap_or_default())
+        }
+
+        async fn remove_sticker(
+            &self,
+            chat_id: i64,
+            sticker_id: &str,
+        ) -> Result<(), RedisStorageError> {
+            if self.take_forced_failure().is_some() {
+                return Err(Self::forced_redis_error());
+            }
+            let key = get_aliases_key(chat_id);
+            let reverse_key = get_sticker_aliases_key(chat_id);
+            let aliases = self
+                .sticker_aliases
+                .lock()
+                .unwrap()
+                .get_mut(&reverse_key)
+                .map(|stickers| stickers.remove(sticker_id).unwrap_or_default())
+                .unwrap_or_default();
+            if let Some(forward) = self.aliases.lock().unwrap().get_mut(&key) {
+                for alias in &aliases {
+                    forward.remove(alias);
+                }
+            }
+            Ok(())
+        }
+
+        async fn update_dialogue<D>(
+            &self,
+            chat_id: i64,
+            from_id: Option<i64>,
+            dialogue: D,
+        ) -> Result<(), RedisStorageError>
+        where
+            D: Serialize + Send + 'static,
+        {
+            // Writes never deserialize, so any injected failure is surfaced
+            // as a storage error here.
+            if self.take_forced_failure().is_some() {
+                return Err(Self::forced_redis_error());
+            }
+            let value = serde_json::to_string(&dialogue).map_err(RedisStorageError::SerdeError)?;
+            let key = get_dialogues_key(chat_id);
+            let field = get_from_field(from_id);
+            self.dialogues
+                .lock()
+                .unwrap()
+                .entry(key)
+                .or_default()
+                .insert(field, value);
+            Ok(())
+        }
+
+        async fn get_dialogue<D>(
+            &self,
+            chat_id: i64,
+            from_id: Option<i64>,
+        ) -> Result<Option<D>, RedisStorageError>
+        where
+            D: DeserializeOwned,
+        {
+            if let Some(failure) = self.take_forced_failure() {
+                return match failure {
+                    ForcedFailure::Redis => Err(Self::forced_redis_error()),
+                    // A partial/incomplete read: something was stored, but
+                    // it doesn't parse as the requested dialogue type.
+                    ForcedFailure::Malformed => serde_json::from_str::<D>("{")
+                        .map(Some)
+                        .map_err(RedisStorageError::SerdeError),
+                };
+            }
+            let key = get_dialogues_key(chat_id);
+            let field = get_from_field(from_id);
+            let value = self
+                .dialogues
+                .lock()
+                .unwrap()
+                .get(&key)
+                .and_then(|dialogues| dialogues.get(&field))
+                .cloned();
+            value
+                .map(|v| serde_json::from_str::<D>(&v))
+                .transpose()
+                .map_err(RedisStorageError::SerdeError)
+        }
+
+        async fn remove_dialogue(
+            &self,
+            chat_id: i64,
+            from_id: Option<i64>,
+        ) -> Result<(), RedisStorageError> {
+            if self.take_forced_failure().is_some() {
+                return Err(Self::forced_redis_error());
+            }
+            let key = get_dialogues_key(chat_id);
+            let field = get_from_field(from_id);
+            let removed = self
+                .dialogues
+                .lock()
+                .unwrap()
+                .get_mut(&key)
+                .map(|dialogues| dialogues.remove(&field).is_some())
+                .unwrap_or(false);
+            if removed {
+                Ok(())
+            } else {
+                Err(RedisStorageError::DialogueNotFound)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::{ForcedFailure, MockStickerStore};
+    use super::{RedisStorageError, StickerStore, TeloxideDialogueStorage};
+    use serde::{Deserialize, Serialize};
+    use teloxide::dispatching::dialogue::Storage;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Dialogue {
+        state: String,
+    }
+
+    #[tokio::test]
+    async fn set_and_get_alias() {
+        let store = MockStickerStore::new();
+        store.set_alias(1, "cat", "sticker_a").await.unwrap();
+        assert_eq!(
+            store.get_sticker_id(1, "cat").await.unwrap(),
+            Some("sticker_a".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn aliases_are_scoped_per_chat() {
+        let store = MockStickerStore::new();
+        store.set_alias(1, "cat", "sticker_a").await.unwrap();
+        assert_eq!(store.get_sticker_id(2, "cat").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn remove_alias_drops_forward_and_reverse_entries() {
+        let store = MockStickerStore::new();
+        store.set_alias(1, "cat", "sticker_a").await.unwrap();
+        store.remove_alias(1, "cat").await.unwrap();
+        assert_eq!(store.get_sticker_id(1, "cat").await.unwrap(), None);
+        assert_eq!(
+            store.get_aliases_for_sticker(1, "sticker_a").await.unwrap(),
+            Vec::<String>::new()
+        );
+    }
+
+    #[tokio::test]
+    async fn reassigning_an_alias_moves_it_between_reverse_entries() {
+        let store = MockStickerStore::new();
+        store.set_alias(1, "cat", "sticker_a").await.unwrap();
+        store.set_alias(1, "cat", "sticker_b").await.unwrap();
+        assert_eq!(
+            store.get_aliases_for_sticker(1, "sticker_a").await.unwrap(),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            store.get_aliases_for_sticker(1, "sticker_b").await.unwrap(),
+            vec!["cat".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn remove_sticker_drops_every_alias_pointing_at_it() {
+        let store = MockStickerStore::new();
+        store.set_aliases(1, &["cat", "kitty"], "sticker_a").await.unwrap();
+        store.remove_sticker(1, "sticker_a").await.unwrap();
+        assert_eq!(store.get_sticker_id(1, "cat").await.unwrap(), None);
+        assert_eq!(store.get_sticker_id(1, "kitty").await.unwrap(), None);
+        assert_eq!(
+            store.get_aliases_for_sticker(1, "sticker_a").await.unwrap(),
+            Vec::<String>::new()
+        );
+    }
+
+    #[tokio::test]
+    async fn forced_redis_failure_surfaces_as_an_error() {
+        let store = MockStickerStore::new();
+        store.inject_failure(ForcedFailure::Redis);
+        let err = store.set_alias(1, "cat", "sticker_a").await.unwrap_err();
+        assert!(matches!(err, RedisStorageError::RedisError(_)));
+        // The failure is consumed, not sticky.
+        store.set_alias(1, "cat", "sticker_a").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn forced_malformed_failure_reports_alias_as_unmapped() {
+        let store = MockStickerStore::new();
+        store.set_alias(1, "cat", "sticker_a").await.unwrap();
+        store.inject_failure(ForcedFailure::Malformed);
+        assert_eq!(store.get_sticker_id(1, "cat").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn dialogue_roundtrip() {
+        let store = MockStickerStore::new();
+        let dialogue = Dialogue { state: "awaiting_alias".to_string() };
+        store.update_dialogue(1, Some(42), dialogue).await.unwrap();
+        let fetched: Option<Dialogue> = store.get_dialogue(1, Some(42)).await.unwrap();
+        assert_eq!(fetched, Some(Dialogue { state: "awaiting_alias".to_string() }));
+        store.remove_dialogue(1, Some(42)).await.unwrap();
+        let fetched: Option<Dialogue> = store.get_dialogue(1, Some(42)).await.unwrap();
+        assert_eq!(fetched, None);
+    }
+
+    #[tokio::test]
+    async fn remove_dialogue_reports_not_found_when_nothing_was_stored() {
+        let store = MockStickerStore::new();
+        let err = store.remove_dialogue(1, None).await.unwrap_err();
+        assert!(matches!(err, RedisStorageError::DialogueNotFound));
+    }
+
+    #[tokio::test]
+    async fn dialogue_key_is_stable_and_collision_free_across_supergroup_ids() {
+        let storage = TeloxideDialogueStorage::new(std::sync::Arc::new(MockStickerStore::new()));
+
+        // Two supergroup ids that collide under a `chat_id << 32 | from_id`
+        // packing scheme (they share the same low 32 bits).
+        let chat_a = -1_000_000_000_000i64;
+        let chat_b = -1_004_294_967_296i64;
+
+        let key_a = storage.dialogue_key(chat_a, Some(7));
+        let key_b = storage.dialogue_key(chat_b, Some(7));
+        assert_ne!(key_a, key_b);
+        // Looking the same pair up again returns the same key.
+        assert_eq!(storage.dialogue_key(chat_a, Some(7)), key_a);
+
+        let dialogue = Dialogue { state: "a".to_string() };
+        Storage::update_dialogue(storage.clone(), key_a, dialogue).await.unwrap();
+        let fetched: Option<Dialogue> = Storage::get_dialogue(storage.clone(), key_b).await.unwrap();
+        assert_eq!(fetched, None, "chat_b must not see chat_a's dialogue");
+        let fetched: Option<Dialogue> = Storage::get_dialogue(storage.clone(), key_a).await.unwrap();
+        assert_eq!(fetched, Some(Dialogue { state: "a".to_string() }));
+    }
+
+    #[tokio::test]
+    async fn remove_dialogue_evicts_the_key_from_the_registry() {
+        let storage = TeloxideDialogueStorage::new(std::sync::Arc::new(MockStickerStore::new()));
+        let key = storage.dialogue_key(1, Some(7));
+
+        let dialogue = Dialogue { state: "a".to_string() };
+        Storage::update_dialogue(storage.clone(), key, dialogue).await.unwrap();
+        Storage::<Dialogue>::remove_dialogue(storage.clone(), key).await.unwrap();
+
+        // The key is gone from the registry, so looking it up again (as
+        // the dispatcher would on the next update for a chat it has
+        // forgotten) is reported as unknown rather than resolving stale.
+        let err = Storage::<Dialogue>::get_dialogue(storage.clone(), key)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RedisStorageError::UnknownDialogueKey));
+
+        // Re-deriving the key for the same pair allocates a fresh one.
+        let new_key = storage.dialogue_key(1, Some(7));
+        assert_ne!(new_key, key);
+    }
+}
@@ -0,0 +1,4 @@
+/// Format a log message, tagging it with the chat it concerns.
+pub fn format_log_chat(message: &str, chat_id: i64) -> String {
+    format!("[chat {}] {}", chat_id, message)
+}